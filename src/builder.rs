@@ -0,0 +1,361 @@
+//! Builder for configuring which [`CepService`]s are used to resolve an address.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::cache::{normalize_cep, Cache};
+use crate::error::Source::{self, LagoinhaLib};
+use crate::error::{Error, Kind};
+use crate::ratelimit::TokenBucket;
+use crate::reconcile::{self, MergedAddress, Strategy};
+use crate::services::cepla::CeplaService;
+use crate::services::correios::CorreiosService;
+use crate::services::viacep::ViaCepService;
+use crate::services::{Address, CepService};
+use crate::transport::{Transport, TransportConfig};
+
+use futures::channel::mpsc;
+use futures::{future::FutureExt, sink::SinkExt, stream::StreamExt};
+use futures_timer::Delay;
+
+const SEND_ERROR: &str =
+    "Failed awaiting channel send. This should not happen. Please contact the developer";
+
+/// The per-service dispatch future, paired with the channel its results are sent over.
+type Dispatch<'a> = (
+    std::pin::Pin<Box<dyn std::future::Future<Output = ()> + 'a>>,
+    mpsc::Receiver<Result<(Source, Address), Error>>,
+);
+
+/// A cache registration: the [`Cache`] implementation plus the TTL to store new entries with.
+struct CacheConfig {
+    cache: Box<dyn Cache>,
+    ttl: Duration,
+}
+
+/// A configured pool of [`CepService`]s, ready to resolve addresses.
+///
+/// Build one with [`Lagoinha::builder`].
+pub struct Lagoinha {
+    services: Vec<Box<dyn CepService>>,
+    error_timeout: Option<Duration>,
+    cache: Option<CacheConfig>,
+    rate_limits: HashMap<Source, TokenBucket>,
+    strategy: Strategy,
+}
+
+impl Lagoinha {
+    /// Starts building a [`Lagoinha`] with the default services (ViaCEP, CepLá and Correios).
+    pub fn builder() -> LagoinhaBuilder {
+        LagoinhaBuilder::new()
+    }
+
+    /// Looks up the `Address` for `cep`, racing every configured service concurrently and
+    /// returning the result from the first one to respond successfully.
+    ///
+    /// If a cache was registered on the builder and already holds an entry for `cep`, this
+    /// returns it directly without contacting any service. Only successful lookups are cached.
+    ///
+    /// If `error_timeout` was set on the builder and no service has produced an `Ok` response
+    /// by the deadline, this returns a [`Kind::Timeout`] error instead of waiting forever.
+    ///
+    /// A service whose rate-limit bucket is empty is skipped for this call and recorded as a
+    /// [`Kind::RateLimited`] error rather than being requested.
+    pub async fn get_address(&self, cep: &str) -> Result<Address, Error> {
+        let normalized_cep = normalize_cep(cep);
+
+        if let Some(cache) = &self.cache {
+            if let Some(address) = cache.cache.get(&normalized_cep).await {
+                return Ok(address);
+            }
+        }
+
+        let result = self.fetch_address(cep).await;
+
+        if let (Ok(address), Some(cache)) = (&result, &self.cache) {
+            cache.cache.put(&normalized_cep, address.clone(), cache.ttl).await;
+        }
+
+        result
+    }
+
+    /// Looks up the `Address` for `cep`, awaiting every configured service (up to
+    /// `error_timeout`, if set) and reconciling their responses field-by-field per
+    /// [`Strategy::Merge`], instead of returning only the first one to respond.
+    ///
+    /// Use [`LagoinhaBuilder::strategy`] to configure the source-priority order; if the builder
+    /// was left at the default [`Strategy::FirstWins`], responses are prioritized in the order
+    /// the services were registered. If only one service answers, this reduces to `FirstWins`
+    /// semantics. The returned [`MergedAddress`] records which [`Source`] contributed each
+    /// field.
+    pub async fn get_merged_address(&self, cep: &str) -> Result<MergedAddress, Error> {
+        let priority: Vec<Source> = match &self.strategy {
+            Strategy::Merge { priority } => priority.clone(),
+            Strategy::FirstWins => self.services.iter().map(|service| service.source()).collect(),
+        };
+
+        let responses = self.fetch_all(cep).await?;
+        Ok(reconcile::merge(responses, &priority))
+    }
+
+    /// Races every configured service for `cep`, tagging each dispatched future with its
+    /// [`Source`] so callers can tell which service a given response came from.
+    fn dispatch<'a>(&'a self, cep: &'a str) -> Dispatch<'a> {
+        let service_count = self.services.len();
+        let (tx, rx) = mpsc::channel::<Result<(Source, Address), Error>>(service_count.max(1));
+
+        let dispatch = futures::future::join_all(self.services.iter().map(|service| {
+            let mut tx = tx.clone();
+            async move {
+                let source = service.source();
+                let allowed = self
+                    .rate_limits
+                    .get(&source)
+                    .map(|bucket| bucket.try_acquire())
+                    .unwrap_or(true);
+
+                let result = if allowed {
+                    service.request(cep).await.map(|address| (source, address))
+                } else {
+                    Err(Error {
+                        source,
+                        kind: Kind::RateLimited,
+                    })
+                };
+
+                tx.send(result)
+                    .await
+                    .map_err(|e| println!("{} with error: {}", SEND_ERROR, e))
+                    .ok();
+            }
+        }))
+        .map(|_| ());
+        drop(tx);
+
+        (Box::pin(dispatch), rx)
+    }
+
+    async fn fetch_address(&self, cep: &str) -> Result<Address, Error> {
+        let service_count = self.services.len();
+        let (dispatch, rx) = self.dispatch(cep);
+        let collect = collect_responses(rx, service_count);
+
+        match self.error_timeout {
+            None => {
+                let mut dispatch = dispatch.fuse();
+                let mut collect = Box::pin(collect.fuse());
+
+                loop {
+                    futures::select! {
+                        () = dispatch => {},
+                        result = collect => return result,
+                    }
+                }
+            }
+            Some(timeout) => {
+                let mut dispatch = dispatch.fuse();
+                let mut collect = Box::pin(collect.fuse());
+                let mut timer = Delay::new(timeout).fuse();
+
+                loop {
+                    futures::select! {
+                        () = dispatch => {},
+                        result = collect => return result,
+                        () = timer => {
+                            return Err(Error {
+                                source: LagoinhaLib,
+                                kind: Kind::Timeout,
+                            })
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Awaits every configured service for `cep`, stopping early at `error_timeout` (if set) and
+    /// returning whatever responses arrived before the deadline.
+    async fn fetch_all(&self, cep: &str) -> Result<Vec<(Source, Address)>, Error> {
+        let service_count = self.services.len();
+        let (dispatch, mut rx) = self.dispatch(cep);
+        let mut dispatch = dispatch.fuse();
+
+        let timer: std::pin::Pin<Box<dyn std::future::Future<Output = ()>>> = match self.error_timeout {
+            Some(timeout) => Box::pin(Delay::new(timeout)),
+            None => Box::pin(futures::future::pending()),
+        };
+        let mut timer = timer.fuse();
+
+        let mut oks: Vec<(Source, Address)> = Vec::new();
+        let mut error_list: Vec<Error> = Vec::new();
+
+        while oks.len() + error_list.len() < service_count {
+            futures::select! {
+                () = dispatch => {},
+                next = rx.next() => match next {
+                    Some(Ok((source, address))) => oks.push((source, address)),
+                    Some(Err(e)) => error_list.push(e),
+                    None => break,
+                },
+                () = timer => break,
+            }
+        }
+
+        if oks.is_empty() {
+            return Err(all_services_error(error_list));
+        }
+
+        Ok(oks)
+    }
+}
+
+/// Reads from `rx` until either a service succeeds or every service has reported an error,
+/// tolerating fewer than `service_count` responses (e.g. if a future was never polled to
+/// completion).
+async fn collect_responses(
+    mut rx: mpsc::Receiver<Result<(Source, Address), Error>>,
+    service_count: usize,
+) -> Result<Address, Error> {
+    let mut error_list: Vec<Error> = Vec::new();
+
+    while error_list.len() < service_count {
+        match rx.next().await {
+            Some(Ok((_, addr))) => return Ok(addr),
+            Some(Err(e)) => error_list.push(e),
+            None => break,
+        }
+    }
+
+    Err(all_services_error(error_list))
+}
+
+/// Builds the [`Kind::AllServicesRetunedErrors`] error reported when every service failed (or
+/// was skipped), carrying one entry per error actually received.
+fn all_services_error(error_list: Vec<Error>) -> Error {
+    Error {
+        source: LagoinhaLib,
+        kind: Kind::AllServicesRetunedErrors { errors: error_list },
+    }
+}
+
+/// Builds a [`Lagoinha`] pool, starting from the three built-in services.
+///
+/// Use [`with_service`](LagoinhaBuilder::with_service) to register a custom
+/// [`CepService`] and [`without_default`](LagoinhaBuilder::without_default) to drop one of the
+/// built-in ones.
+pub struct LagoinhaBuilder {
+    default_services: Vec<Source>,
+    services: Vec<Box<dyn CepService>>,
+    error_timeout: Option<Duration>,
+    cache: Option<CacheConfig>,
+    rate_limits: HashMap<Source, TokenBucket>,
+    transport_config: TransportConfig,
+    strategy: Strategy,
+}
+
+impl LagoinhaBuilder {
+    fn new() -> Self {
+        let mut rate_limits = HashMap::new();
+        rate_limits.insert(Source::Viacep, TokenBucket::new(10, 5.0));
+        rate_limits.insert(Source::Correios, TokenBucket::new(10, 5.0));
+        // CepLá runs through curl rather than the shared Hyper client and has been observed to
+        // throttle more aggressive clients, so it gets a stricter default bucket.
+        rate_limits.insert(Source::Cepla, TokenBucket::new(5, 1.0));
+
+        LagoinhaBuilder {
+            default_services: vec![Source::Viacep, Source::Cepla, Source::Correios],
+            services: Vec::new(),
+            error_timeout: None,
+            cache: None,
+            rate_limits,
+            transport_config: TransportConfig::default(),
+            strategy: Strategy::default(),
+        }
+    }
+
+    /// Registers an additional service to race alongside the enabled ones.
+    pub fn with_service(mut self, service: Box<dyn CepService>) -> Self {
+        self.services.push(service);
+        self
+    }
+
+    /// Removes a built-in service (identified by its [`Source`](crate::error::Source)) from the
+    /// pool, e.g. `without_default(Source::Correios)`.
+    pub fn without_default(mut self, source: crate::error::Source) -> Self {
+        self.default_services.retain(|default| *default != source);
+        self
+    }
+
+    /// Configures the [`Transport`] shared by the built-in services: connect/read timeouts, a
+    /// custom `User-Agent` and an optional proxy. Defaults to [`TransportConfig::default`] (no
+    /// timeouts, default curl/Hyper behavior) if never called.
+    pub fn with_transport(mut self, config: TransportConfig) -> Self {
+        self.transport_config = config;
+        self
+    }
+
+    /// Bounds [`Lagoinha::get_address`] calls: if no service has returned a successful response
+    /// within `timeout`, the call fails with [`Kind::Timeout`] instead of hanging.
+    pub fn error_timeout(mut self, timeout: Duration) -> Self {
+        self.error_timeout = Some(timeout);
+        self
+    }
+
+    /// Registers a [`Cache`] in front of the service pool; successful lookups are stored in it
+    /// for `ttl` and served from it on subsequent calls without contacting any service.
+    pub fn with_cache(mut self, cache: Box<dyn Cache>, ttl: Duration) -> Self {
+        self.cache = Some(CacheConfig { cache, ttl });
+        self
+    }
+
+    /// Sets (or replaces) the token-bucket rate limit for `source`: at most `capacity` requests
+    /// may be in flight before the bucket empties, refilling at `refill_per_sec` tokens/second.
+    ///
+    /// Each of the three built-in services has a default bucket already; call this to tune one
+    /// of them or to rate-limit a custom service registered with
+    /// [`with_service`](LagoinhaBuilder::with_service).
+    pub fn with_rate_limit(mut self, source: Source, capacity: u32, refill_per_sec: f64) -> Self {
+        self.rate_limits
+            .insert(source, TokenBucket::new(capacity, refill_per_sec));
+        self
+    }
+
+    /// Removes the rate limit for `source`, so it is always requested rather than risking a
+    /// [`Kind::RateLimited`] skip.
+    pub fn without_rate_limit(mut self, source: Source) -> Self {
+        self.rate_limits.remove(&source);
+        self
+    }
+
+    /// Sets the source-priority order used by [`Lagoinha::get_merged_address`]. Has no effect on
+    /// [`Lagoinha::get_address`], which always uses `FirstWins` semantics.
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Finishes configuration and produces a [`Lagoinha`] pool.
+    pub fn build(self) -> Lagoinha {
+        let transport = Transport::new(self.transport_config);
+
+        let mut services: Vec<Box<dyn CepService>> = self
+            .default_services
+            .iter()
+            .filter_map(|source| match source {
+                Source::Viacep => Some(Box::new(ViaCepService::new(transport.clone())) as Box<dyn CepService>),
+                Source::Cepla => Some(Box::new(CeplaService::new(transport.clone())) as Box<dyn CepService>),
+                Source::Correios => Some(Box::new(CorreiosService::new(transport.clone())) as Box<dyn CepService>),
+                Source::LagoinhaLib => None,
+            })
+            .collect();
+        services.extend(self.services);
+
+        Lagoinha {
+            services,
+            error_timeout: self.error_timeout,
+            cache: self.cache,
+            rate_limits: self.rate_limits,
+            strategy: self.strategy,
+        }
+    }
+}
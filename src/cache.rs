@@ -0,0 +1,90 @@
+//! Optional caching layer sitting in front of [`crate::Lagoinha::get_address`].
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::services::Address;
+
+/// A cache keyed by normalized CEP (see [`normalize_cep`]).
+///
+/// Ship your own implementation (e.g. backed by Redis or disk) to share lookups across
+/// processes; register it with
+/// [`LagoinhaBuilder::with_cache`](crate::builder::LagoinhaBuilder::with_cache).
+#[async_trait::async_trait]
+pub trait Cache: Send + Sync {
+    /// Returns the cached `Address` for `cep`, if present and not expired.
+    async fn get(&self, cep: &str) -> Option<Address>;
+    /// Stores `address` for `cep`, to be evicted after `ttl`.
+    async fn put(&self, cep: &str, address: Address, ttl: Duration);
+}
+
+/// Strips the optional dash and any whitespace from a CEP, so `"70150-903"` and `"70150903"`
+/// key the same cache entry.
+pub fn normalize_cep(cep: &str) -> String {
+    cep.chars().filter(|c| !c.is_whitespace() && *c != '-').collect()
+}
+
+/// The default [`Cache`] implementation: a simple in-memory map with per-entry TTLs.
+#[derive(Default)]
+pub struct InMemoryCache {
+    entries: Mutex<HashMap<String, (Address, Instant)>>,
+}
+
+impl InMemoryCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        InMemoryCache::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl Cache for InMemoryCache {
+    async fn get(&self, cep: &str) -> Option<Address> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(cep) {
+            Some((address, expires_at)) if *expires_at > Instant::now() => Some(address.clone()),
+            Some(_) => {
+                entries.remove(cep);
+                None
+            }
+            None => None,
+        }
+    }
+
+    async fn put(&self, cep: &str, address: Address, ttl: Duration) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(cep.to_string(), (address, Instant::now() + ttl));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_cep_strips_dash_and_whitespace() {
+        assert_eq!(normalize_cep("70150-903"), "70150903");
+        assert_eq!(normalize_cep(" 70150 903 "), "70150903");
+        assert_eq!(normalize_cep("70150903"), "70150903");
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_expires_entries_after_ttl() {
+        let cache = InMemoryCache::new();
+        let address = Address {
+            cep: "70150903".to_string(),
+            address: "SPP".to_string(),
+            details: "".to_string(),
+            neighborhood: "Zona Cívico-Administrativa".to_string(),
+            city: "Brasília".to_string(),
+            state: "DF".to_string(),
+        };
+
+        cache.put("70150903", address.clone(), Duration::from_secs(0)).await;
+        assert!(cache.get("70150903").await.is_none());
+
+        cache.put("70150903", address, Duration::from_secs(60)).await;
+        assert!(cache.get("70150903").await.is_some());
+    }
+}
@@ -0,0 +1,88 @@
+//! Error types returned by the crate and by the individual CEP services.
+
+use std::fmt;
+
+/// Identifies which service (or the library itself) produced an [`Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Source {
+    LagoinhaLib,
+    Viacep,
+    Cepla,
+    Correios,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::LagoinhaLib => write!(f, "lagoinha"),
+            Source::Viacep => write!(f, "viacep"),
+            Source::Cepla => write!(f, "cepla"),
+            Source::Correios => write!(f, "correios"),
+        }
+    }
+}
+
+/// The specific failure that occurred while resolving a CEP.
+#[derive(Debug)]
+pub enum Kind {
+    UnexpectedLibraryError,
+    MissingBodyError,
+    ClientError { code: u16 },
+    ServerError { code: u16 },
+    UnknownServerError { code: u16 },
+    BodyParsingError { error: String, body: String },
+    /// No service returned a successful response before `error_timeout` elapsed, or (with a
+    /// service as the source) that service's transport connect/read timeout elapsed.
+    Timeout,
+    /// The service's token bucket had no tokens available, so the request was skipped for this
+    /// call rather than risking the provider throttling or blocking the client.
+    RateLimited,
+    /// No service returned a successful response; holds one entry per error received, in the
+    /// order it arrived. A service skipped by rate limiting counts as an error; a service that
+    /// had not answered when `error_timeout` cut the wait short has no entry.
+    AllServicesRetunedErrors { errors: Vec<Error> },
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kind::UnexpectedLibraryError => write!(f, "unexpected library error"),
+            Kind::MissingBodyError => write!(f, "response body was missing"),
+            Kind::ClientError { code } => write!(f, "client error, status code {}", code),
+            Kind::ServerError { code } => write!(f, "server error, status code {}", code),
+            Kind::UnknownServerError { code } => {
+                write!(f, "unknown server error, status code {}", code)
+            }
+            Kind::BodyParsingError { error, body } => {
+                write!(f, "failed to parse response body: {} (body: {})", error, body)
+            }
+            Kind::Timeout => write!(f, "timed out waiting for a service to respond"),
+            Kind::RateLimited => write!(f, "skipped: rate limit exceeded for this service"),
+            Kind::AllServicesRetunedErrors { errors } => {
+                write!(f, "all services returned errors: ")?;
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The error type returned by [`crate::get_address`] and by each service's `request` function.
+#[derive(Debug)]
+pub struct Error {
+    pub kind: Kind,
+    pub source: Source,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.source, self.kind)
+    }
+}
+
+impl std::error::Error for Error {}
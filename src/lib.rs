@@ -5,9 +5,11 @@
 //! # Services
 //!
 //! Currenlty the services used are : correios, viacep and cepla
-//! It is expected to support adding a custom service to the pool in the future, and the ability to disable the default ones.
+//! Custom services can be registered, and the default ones disabled, through [`Lagoinha::builder`].
 //!
-//! While the default http library is Hyper, the CepLá service has an issue with its header implementation, and so the curl library was used. More information in the docs for this service.
+//! Every service shares one configurable [`transport::Transport`] (timeouts, `User-Agent`, proxy). CepLá has an issue with its header implementation that makes Hyper unusable for it, so its transport call is routed through curl instead. More information in the docs for this service.
+//!
+//! By default the first successful response wins and the rest are discarded. To reconcile every service's response into a single `Address` field-by-field instead, see [`Lagoinha::get_merged_address`] and [`reconcile::Strategy::Merge`].
 //!
 //! # Example
 //! ```
@@ -15,121 +17,41 @@
 //!extern crate tokio;
 //!
 //!#[tokio::main]
-//!async fn main() {    
-//!    let addr = lagoinha::get_address("70150903").await;
+//!async fn main() {
+//!    let addr = lagoinha::get_address("70150903", None).await;
 //!    println!("{:#?}", addr);
 //!}
 //!```
 //!
 
+pub mod builder;
+pub mod cache;
 pub mod error;
+pub mod ratelimit;
+pub mod reconcile;
 pub mod services;
+pub mod transport;
 use error::Error;
-use error::Source::LagoinhaLib;
 use services::Address;
 
-use futures::channel::mpsc;
-use futures::{future::FutureExt, sink::SinkExt};
-
-const SEND_ERROR: &str =
-    "Failed awaiting channel send. This should not happen. Please contact the developer";
-
-async fn viacep_requet(cep: &str, mut tx: mpsc::Sender<Result<services::Address, Error>>) {
-    let addr = services::viacep::request(cep).await;
-    match addr {
-        Ok(addr) => {
-            tx.send(Ok(addr.to_address()))
-                .await
-                .map_err(|e| println!("{} with error: {}", SEND_ERROR, e.to_string()))
-                .ok();
-        }
-        Err(err) => {
-            tx.send(Err(err))
-                .await
-                .map_err(|e| println!("{} with error: {}", SEND_ERROR, e.to_string()))
-                .ok();
-        }
-    }
-}
+use std::time::Duration;
 
-async fn cepla_requet(cep: &str, mut tx: mpsc::Sender<Result<services::Address, Error>>) {
-    let addr = services::cepla::request(cep).await;
-    match addr {
-        Ok(addr) => {
-            tx.send(Ok(addr.to_address()))
-                .await
-                .map_err(|e| println!("{} with error: {}", SEND_ERROR, e.to_string()))
-                .ok();
-        }
-        Err(err) => {
-            tx.send(Err(err))
-                .await
-                .map_err(|e| println!("{} with error: {}", SEND_ERROR, e.to_string()))
-                .ok();
-        }
-    }
-}
+pub use builder::Lagoinha;
 
-async fn correios_requet(cep: &str, mut tx: mpsc::Sender<Result<services::Address, Error>>) {
-    let addr = services::correios::request(cep).await;
-    match addr {
-        Ok(addr) => {
-            tx.send(Ok(addr.to_address()))
-                .await
-                .map_err(|e| println!("{} with error: {}", SEND_ERROR, e.to_string()))
-                .ok();
-        }
-        Err(err) => {
-            tx.send(Err(err))
-                .await
-                .map_err(|e| println!("{} with error: {}", SEND_ERROR, e.to_string()))
-                .ok();
-        }
+/// Looks up the `Address` for `cep` using the default service pool (ViaCEP, CepLá and Correios),
+/// racing them concurrently and returning the result from the first one to respond successfully.
+///
+/// `error_timeout`, when set, bounds the overall call: if no service has produced an `Ok`
+/// response by the deadline (e.g. because one of them is hanging on a dead endpoint), the call
+/// returns a [`error::Kind::Timeout`] error instead of waiting forever.
+///
+/// To register custom services or disable the default ones, use [`Lagoinha::builder`] instead.
+pub async fn get_address(cep: &str, error_timeout: Option<Duration>) -> Result<Address, Error> {
+    let mut builder = Lagoinha::builder();
+    if let Some(timeout) = error_timeout {
+        builder = builder.error_timeout(timeout);
     }
-}
-
-pub async fn get_address(cep: &str) -> Result<Address, Error> {
-    let (tx, mut rx) = mpsc::channel::<Result<services::Address, Error>>(1);
-
-    futures::select! {
-        () = viacep_requet(cep, tx.clone()).fuse() => "viacep",
-        () = cepla_requet(cep, tx.clone()).fuse() => "cepla",
-        () = correios_requet(cep, tx.clone()).fuse() => "correios",
-        default => unreachable!()
-    };
-
-    let mut error_list: Vec<Error> = Vec::new();
-
-    for _ in 0..2 {
-        let read = rx.try_next();
-        match read {
-            Ok(read_address) => match read_address {
-                Some(read_address) => match read_address {
-                    Ok(addr) => return Ok(addr),
-                    Err(e) => error_list.push(e),
-                },
-                None => error_list.push(Error {
-                    kind: error::Kind::UnexpectedLibraryError,
-                    source: LagoinhaLib,
-                }),
-            },
-            Err(_) => {
-                return Err(Error {
-                    kind: error::Kind::UnexpectedLibraryError,
-                    source: LagoinhaLib,
-                })
-            }
-        };
-    }
-
-    Err(Error {
-        source: error::Source::LagoinhaLib,
-        kind: error::Kind::AllServicesRetunedErrors {
-            e1: format!("{}", error_list[0]),
-            e2: format!("{}", error_list[1]),
-            e3: format!("{}", error_list[2]),
-        },
-    })
+    builder.build().get_address(cep).await
 }
 
 #[cfg(test)]
@@ -146,7 +68,7 @@ mod tests {
             state: "DF".to_string(),
         };
 
-        let recv_addr = super::get_address("70150903").await.unwrap();
+        let recv_addr = super::get_address("70150903", None).await.unwrap();
         assert_eq!(addr.city, recv_addr.city);
         assert_eq!(addr.state, recv_addr.state);
         assert_eq!(addr.neighborhood, recv_addr.neighborhood);
@@ -0,0 +1,73 @@
+//! Per-service token-bucket rate limiting, so the crate stays a good citizen under high QPS
+//! against CEP providers that throttle aggressive clients.
+
+use std::sync::Mutex;
+use std::time::Instant;
+
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// A token bucket holding `capacity` tokens, refilled at `refill_per_sec` tokens/second.
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    state: Mutex<BucketState>,
+}
+
+impl TokenBucket {
+    /// Creates a bucket starting full, with `capacity` tokens refilled at `refill_per_sec`
+    /// tokens/second.
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        TokenBucket {
+            capacity: capacity as f64,
+            refill_per_sec,
+            state: Mutex::new(BucketState {
+                tokens: capacity as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Attempts to take one token, returning `true` if one was available.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+
+        let elapsed = state.last_refill.elapsed().as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        state.last_refill = Instant::now();
+
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    #[test]
+    fn exhausts_after_capacity_requests() {
+        let bucket = TokenBucket::new(2, 0.0);
+        assert!(bucket.try_acquire());
+        assert!(bucket.try_acquire());
+        assert!(!bucket.try_acquire());
+    }
+
+    #[test]
+    fn refills_over_time() {
+        let bucket = TokenBucket::new(1, 1000.0);
+        assert!(bucket.try_acquire());
+        assert!(!bucket.try_acquire());
+
+        sleep(Duration::from_millis(10));
+        assert!(bucket.try_acquire());
+    }
+}
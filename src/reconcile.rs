@@ -0,0 +1,135 @@
+//! Reconciliation strategies for combining multiple services' responses into one [`Address`].
+
+use crate::error::Source;
+use crate::services::Address;
+
+/// The source-priority order [`crate::Lagoinha::get_merged_address`] uses when reconciling the
+/// responses from every service. [`crate::Lagoinha::get_address`] ignores it.
+#[derive(Debug, Clone, Default)]
+pub enum Strategy {
+    /// The default. [`crate::Lagoinha::get_merged_address`] still merges every response, giving
+    /// priority to the services in the order they were registered.
+    /// [`crate::Lagoinha::get_address`] returns the first successful response and discards the
+    /// rest, whatever the strategy.
+    #[default]
+    FirstWins,
+    /// Awaits every service (up to `error_timeout`, if set) and builds one [`Address`] by
+    /// filling each field from whichever response supplied a non-empty value, preferring
+    /// `priority` order when several services disagree. Falls back to `FirstWins` semantics if
+    /// only one service answers.
+    Merge {
+        /// Sources listed earlier win field-level conflicts; a source not listed here is
+        /// considered lowest priority, in the order its response arrived.
+        priority: Vec<Source>,
+    },
+}
+
+/// An [`Address`] merged from multiple services' responses, recording which [`Source`]
+/// contributed each field (`None` if no service supplied a non-empty value for it).
+#[derive(Debug, Clone, Default)]
+pub struct MergedAddress {
+    pub address: Address,
+    pub cep_source: Option<Source>,
+    pub address_source: Option<Source>,
+    pub details_source: Option<Source>,
+    pub neighborhood_source: Option<Source>,
+    pub city_source: Option<Source>,
+    pub state_source: Option<Source>,
+}
+
+/// Builds a [`MergedAddress`] from `responses`, preferring `priority` order when more than one
+/// response has a non-empty value for the same field.
+pub(crate) fn merge(mut responses: Vec<(Source, Address)>, priority: &[Source]) -> MergedAddress {
+    responses.sort_by_key(|(source, _)| {
+        priority.iter().position(|p| p == source).unwrap_or(priority.len())
+    });
+
+    let mut merged = MergedAddress::default();
+    for (source, address) in responses {
+        fill(&mut merged.address.cep, &mut merged.cep_source, address.cep, source);
+        fill(&mut merged.address.address, &mut merged.address_source, address.address, source);
+        fill(&mut merged.address.details, &mut merged.details_source, address.details, source);
+        fill(
+            &mut merged.address.neighborhood,
+            &mut merged.neighborhood_source,
+            address.neighborhood,
+            source,
+        );
+        fill(&mut merged.address.city, &mut merged.city_source, address.city, source);
+        fill(&mut merged.address.state, &mut merged.state_source, address.state, source);
+    }
+
+    merged
+}
+
+/// Sets `field` and `field_source` to `value`/`source` if `field` is still empty and `value`
+/// isn't.
+fn fill(field: &mut String, field_source: &mut Option<Source>, value: String, source: Source) {
+    if field.is_empty() && !value.is_empty() {
+        *field = value;
+        *field_source = Some(source);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_missing_fields_from_lower_priority_source() {
+        let viacep = Address {
+            cep: "70150903".to_string(),
+            address: "".to_string(),
+            details: "".to_string(),
+            neighborhood: "Zona Cívico-Administrativa".to_string(),
+            city: "Brasília".to_string(),
+            state: "DF".to_string(),
+        };
+        let correios = Address {
+            cep: "70150903".to_string(),
+            address: "SPP".to_string(),
+            details: "".to_string(),
+            neighborhood: "".to_string(),
+            city: "Brasília".to_string(),
+            state: "DF".to_string(),
+        };
+
+        let merged = merge(
+            vec![(Source::Viacep, viacep), (Source::Correios, correios)],
+            &[Source::Viacep, Source::Correios],
+        );
+
+        assert_eq!(merged.address.address, "SPP");
+        assert_eq!(merged.address_source, Some(Source::Correios));
+        assert_eq!(merged.address.neighborhood, "Zona Cívico-Administrativa");
+        assert_eq!(merged.neighborhood_source, Some(Source::Viacep));
+    }
+
+    #[test]
+    fn higher_priority_source_wins_conflicting_field() {
+        let viacep = Address {
+            cep: "70150903".to_string(),
+            address: "from viacep".to_string(),
+            details: "".to_string(),
+            neighborhood: "".to_string(),
+            city: "".to_string(),
+            state: "".to_string(),
+        };
+        let correios = Address {
+            cep: "70150903".to_string(),
+            address: "from correios".to_string(),
+            details: "".to_string(),
+            neighborhood: "".to_string(),
+            city: "".to_string(),
+            state: "".to_string(),
+        };
+
+        let merged = merge(
+            vec![(Source::Correios, correios), (Source::Viacep, viacep)],
+            &[Source::Viacep, Source::Correios],
+        );
+
+        assert_eq!(merged.address.address, "from viacep");
+        assert_eq!(merged.address_source, Some(Source::Viacep));
+    }
+}
@@ -3,104 +3,38 @@
 //! This service has an out os [spec](https://tools.ietf.org/html/rfc2616#section-4.2) header implementation,
 //! and does not comply with the [RFC2616](https://tools.ietf.org/html/rfc2616#section-4.2).
 //! This causes an issue when using it with libraries, like Hyper, because they parse all headers to lower case.
-//! To solve this issue, the Curl library was used.
+//! To solve this issue, requests go through [`crate::transport::Transport`] with
+//! `preserve_header_case` set, which routes them through a curl-backed path instead.
 
-extern crate curl;
 extern crate serde;
 extern crate serde_json;
 
 use crate::error::Error;
 use crate::error::Kind;
 use crate::error::Source::Cepla;
-
-use curl::easy::{Easy, List};
+use crate::transport::{Transport, TransportConfig};
 
 use serde::{Deserialize, Serialize};
 
 /// request function runs the API call to cepla service
 pub async fn request(cep: &str) -> Result<Address, Error> {
-    let mut requester = Easy::new();
-    let uri = format!("http://cep.la/{}", cep);
-    requester.url(&uri).or(Err(Error {
-        kind: Kind::UnexpectedLibraryError,
-        source: Cepla,
-    }))?;
-
-    let mut list = List::new();
-    list.append("Accept: application/json").or(Err(Error {
-        kind: Kind::UnexpectedLibraryError,
-        source: Cepla,
-    }))?;
-
-    requester.http_headers(list).or(Err(Error {
-        kind: Kind::UnexpectedLibraryError,
-        source: Cepla,
-    }))?;
-    let mut buf = Vec::new();
-    {
-        let mut transfer = requester.transfer();
-        transfer
-            .write_function(|new_data| {
-                buf.extend_from_slice(new_data);
-                Ok(new_data.len())
-            })
-            .or(Err(Error {
-                kind: Kind::MissingBodyError,
-                source: Cepla,
-            }))?;
-        transfer.perform().or(Err(Error {
-            kind: Kind::MissingBodyError,
-            source: Cepla,
-        }))?;
-    }
-    match requester.response_code() {
-        Ok(code) => match code {
-            200..=299 => (),
-            400..=499 => {
-                return Err(Error {
-                    kind: Kind::ClientError { code: code as u16 },
-                    source: Cepla,
-                });
-            }
-            500..=599 => {
-                return Err(Error {
-                    kind: Kind::ServerError { code: code as u16 },
-                    source: Cepla,
-                });
-            }
-            _ => {
-                return Err(Error {
-                    kind: Kind::UnknownServerError { code: code as u16 },
-                    source: Cepla,
-                });
-            }
-        },
-        Err(_) => {
-            return Err(Error {
-                kind: Kind::UnexpectedLibraryError,
-                source: Cepla,
-            });
-        }
-    }
+    request_via(&Transport::new(TransportConfig::default()), cep).await
+}
 
-    let address = serde_json::from_slice::<Address>(&buf);
-    match address {
-        Ok(address) => return Ok(address),
-        Err(e) => {
-            let str_body = std::str::from_utf8(&buf);
-            let str_body = match str_body {
-                Ok(str_body) => str_body,
-                Err(_) => "Failed to produce string body ", //+  e.to_string().as_str()},
-            };
-            return Err(Error {
-                kind: Kind::BodyParsingError {
-                    error: e.to_string(),
-                    body: str_body.to_string(),
-                },
-                source: Cepla,
-            });
+async fn request_via(transport: &Transport, cep: &str) -> Result<Address, Error> {
+    let url = format!("http://cep.la/{}", cep);
+    let response = transport.get(&url, true, Cepla).await?;
+
+    serde_json::from_slice::<Address>(&response.body).map_err(|e| {
+        let str_body = std::str::from_utf8(&response.body).unwrap_or("Failed to produce string body");
+        Error {
+            kind: Kind::BodyParsingError {
+                error: e.to_string(),
+                body: str_body.to_string(),
+            },
+            source: Cepla,
         }
-    };
+    })
 }
 
 /// Address struct used to deserialize the results from the cepla API
@@ -120,6 +54,42 @@ pub struct Address {
     pub details: String,
 }
 
+impl Address {
+    /// Converts the service-specific representation into the crate's unified [`crate::services::Address`].
+    pub fn to_address(self) -> crate::services::Address {
+        crate::services::Address {
+            cep: self.cep,
+            address: self.address,
+            details: self.details,
+            neighborhood: self.neighborhood,
+            city: self.city,
+            state: self.state,
+        }
+    }
+}
+
+/// The built-in [`CepService`](crate::services::CepService) backed by [`request_via`], racing
+/// requests through the [`Transport`] it was constructed with.
+pub struct CeplaService(Transport);
+
+impl CeplaService {
+    /// Creates the service, routing its requests through `transport`.
+    pub fn new(transport: Transport) -> Self {
+        CeplaService(transport)
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::services::CepService for CeplaService {
+    async fn request(&self, cep: &str) -> Result<crate::services::Address, Error> {
+        request_via(&self.0, cep).await.map(Address::to_address)
+    }
+
+    fn source(&self) -> crate::error::Source {
+        Cepla
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[tokio::test]
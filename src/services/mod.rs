@@ -0,0 +1,33 @@
+//! The individual CEP lookup services bundled with the crate.
+
+pub mod cepla;
+pub mod correios;
+pub mod viacep;
+
+use crate::error::{Error, Source};
+
+/// A resolved address, normalized to a single shape regardless of which service produced it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Address {
+    pub cep: String,
+    pub address: String,
+    pub details: String,
+    pub neighborhood: String,
+    pub city: String,
+    pub state: String,
+}
+
+/// A pluggable CEP lookup backend.
+///
+/// Implement this to register a custom service with [`crate::Lagoinha`]'s builder, e.g. a
+/// private or paid CEP API, or to swap out one of the built-in services.
+#[async_trait::async_trait]
+pub trait CepService: Send + Sync {
+    /// Performs the lookup for `cep`.
+    async fn request(&self, cep: &str) -> Result<Address, Error>;
+    /// Identifies this service in errors, rate limits and merge priority. For the built-in
+    /// services it is also the key that
+    /// [`LagoinhaBuilder::without_default`](crate::builder::LagoinhaBuilder::without_default)
+    /// removes them by; custom services are not affected by `without_default`.
+    fn source(&self) -> Source;
+}
@@ -0,0 +1,87 @@
+//! ViaCEP service: https://viacep.com.br/
+//!
+//! Plain JSON over HTTP, served through the shared [`crate::transport::Transport`].
+
+extern crate serde;
+extern crate serde_json;
+
+use crate::error::Error;
+use crate::error::Kind;
+use crate::error::Source::Viacep;
+use crate::transport::{Transport, TransportConfig};
+
+use serde::{Deserialize, Serialize};
+
+/// request function runs the API call to viacep service
+pub async fn request(cep: &str) -> Result<Address, Error> {
+    request_via(&Transport::new(TransportConfig::default()), cep).await
+}
+
+async fn request_via(transport: &Transport, cep: &str) -> Result<Address, Error> {
+    let url = format!("http://viacep.com.br/ws/{}/json/", cep);
+    let response = transport.get(&url, false, Viacep).await?;
+
+    serde_json::from_slice::<Address>(&response.body).map_err(|e| {
+        let str_body = std::str::from_utf8(&response.body).unwrap_or("Failed to produce string body");
+        Error {
+            kind: Kind::BodyParsingError {
+                error: e.to_string(),
+                body: str_body.to_string(),
+            },
+            source: Viacep,
+        }
+    })
+}
+
+/// Address struct used to deserialize the results from the viacep API
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Address {
+    #[serde(rename = "cep", default = "String::new")]
+    pub cep: String,
+    #[serde(rename = "uf", default = "String::new")]
+    pub state: String,
+    #[serde(rename = "localidade", default = "String::new")]
+    pub city: String,
+    #[serde(rename = "bairro", default = "String::new")]
+    pub neighborhood: String,
+    #[serde(rename = "logradouro", default = "String::new")]
+    pub address: String,
+    #[serde(rename = "complemento", default = "String::new")]
+    pub details: String,
+}
+
+impl Address {
+    /// Converts the service-specific representation into the crate's unified [`crate::services::Address`].
+    pub fn to_address(self) -> crate::services::Address {
+        crate::services::Address {
+            cep: self.cep,
+            address: self.address,
+            details: self.details,
+            neighborhood: self.neighborhood,
+            city: self.city,
+            state: self.state,
+        }
+    }
+}
+
+/// The built-in [`CepService`](crate::services::CepService) backed by [`request_via`], racing
+/// requests through the [`Transport`] it was constructed with.
+pub struct ViaCepService(Transport);
+
+impl ViaCepService {
+    /// Creates the service, routing its requests through `transport`.
+    pub fn new(transport: Transport) -> Self {
+        ViaCepService(transport)
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::services::CepService for ViaCepService {
+    async fn request(&self, cep: &str) -> Result<crate::services::Address, Error> {
+        request_via(&self.0, cep).await.map(Address::to_address)
+    }
+
+    fn source(&self) -> crate::error::Source {
+        Viacep
+    }
+}
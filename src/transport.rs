@@ -0,0 +1,239 @@
+//! Shared, configurable HTTP transport used by the bundled services.
+//!
+//! Every built-in service is driven by one [`Transport`], configured from a [`TransportConfig`]
+//! with connect/read timeouts, a custom `User-Agent` and an optional proxy. Most requests go
+//! through one shared Hyper client (built once in [`Transport::new`] and reused for every
+//! request, rather than per-call), but [`crate::services::cepla`] passes `preserve_header_case =
+//! true` to [`Transport::get`] because CepLá emits headers that are not
+//! [RFC2616](https://tools.ietf.org/html/rfc2616#section-4.2) case-insensitive and Hyper
+//! lowercases them; that flag routes the request through a curl-backed path that keeps the raw
+//! casing instead.
+
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use curl::easy::{Easy, List};
+use hyper::body::HttpBody as _;
+use hyper::client::HttpConnector;
+use hyper::header::USER_AGENT;
+use hyper::service::Service;
+use hyper::{Client, Request, Uri};
+
+use crate::error::{Error, Kind, Source};
+
+/// Knobs shared by every built-in service, regardless of which backend performs the request.
+#[derive(Clone, Debug, Default)]
+pub struct TransportConfig {
+    pub connect_timeout: Option<Duration>,
+    pub read_timeout: Option<Duration>,
+    pub user_agent: Option<String>,
+    pub proxy: Option<String>,
+}
+
+/// A raw HTTP response: status code plus body bytes, before any service-specific JSON parsing.
+pub struct RawResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+/// An [`HttpConnector`] that, when a proxy is configured, dials the proxy's address for every
+/// connection instead of the request's own host, while leaving the request's absolute-form URI
+/// untouched so the proxy can still route it. This only covers plain `http://` forwarding (every
+/// bundled service talks `http://`, not `https://`), not a `CONNECT` tunnel.
+#[derive(Clone, Debug)]
+struct ProxyConnector {
+    inner: HttpConnector,
+    proxy: Option<Uri>,
+}
+
+impl Service<Uri> for ProxyConnector {
+    type Response = <HttpConnector as Service<Uri>>::Response;
+    type Error = <HttpConnector as Service<Uri>>::Error;
+    type Future = <HttpConnector as Service<Uri>>::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        self.inner.call(self.proxy.clone().unwrap_or(uri))
+    }
+}
+
+/// The shared HTTP transport, configured once and handed to every built-in
+/// [`CepService`](crate::services::CepService).
+#[derive(Clone, Debug)]
+pub struct Transport {
+    config: TransportConfig,
+    client: Client<ProxyConnector>,
+}
+
+impl Default for Transport {
+    fn default() -> Self {
+        Transport::new(TransportConfig::default())
+    }
+}
+
+impl Transport {
+    /// Creates a transport driven by `config`, building the shared Hyper client once so every
+    /// request reuses its connection pool instead of paying a fresh-connection cost each time.
+    pub fn new(config: TransportConfig) -> Self {
+        let proxy = config.proxy.as_deref().and_then(|proxy| proxy.parse().ok());
+        let client = Client::builder().build(ProxyConnector {
+            inner: HttpConnector::new(),
+            proxy,
+        });
+
+        Transport { config, client }
+    }
+
+    /// Performs a GET request against `url`, classifying the response status into the
+    /// appropriate [`Kind`] and attributing any error to `source`.
+    ///
+    /// Set `preserve_header_case` when the caller needs raw, case-sensitive response headers;
+    /// this routes the request through a curl-backed path instead of the shared Hyper client.
+    pub async fn get(
+        &self,
+        url: &str,
+        preserve_header_case: bool,
+        source: Source,
+    ) -> Result<RawResponse, Error> {
+        if preserve_header_case {
+            self.get_curl(url, source).await
+        } else {
+            self.get_hyper(url, source).await
+        }
+    }
+
+    async fn get_hyper(&self, url: &str, source: Source) -> Result<RawResponse, Error> {
+        let mut request = Request::get(url).body(hyper::Body::empty()).or(Err(Error {
+            kind: Kind::UnexpectedLibraryError,
+            source,
+        }))?;
+        if let Some(user_agent) = &self.config.user_agent {
+            let value = user_agent.parse().or(Err(Error {
+                kind: Kind::UnexpectedLibraryError,
+                source,
+            }))?;
+            request.headers_mut().insert(USER_AGENT, value);
+        }
+
+        let fetch = self.client.request(request);
+        let mut resp = match self.config.read_timeout.or(self.config.connect_timeout) {
+            Some(timeout) => tokio::time::timeout(timeout, fetch)
+                .await
+                .or(Err(Error {
+                    kind: Kind::Timeout,
+                    source,
+                }))?
+                .or(Err(Error {
+                    kind: Kind::UnexpectedLibraryError,
+                    source,
+                }))?,
+            None => fetch.await.or(Err(Error {
+                kind: Kind::UnexpectedLibraryError,
+                source,
+            }))?,
+        };
+
+        classify_status(resp.status().as_u16(), source)?;
+
+        let mut buf = Vec::new();
+        while let Some(chunk) = resp.body_mut().data().await {
+            let chunk = chunk.or(Err(Error {
+                kind: Kind::MissingBodyError,
+                source,
+            }))?;
+            buf.extend_from_slice(&chunk);
+        }
+
+        Ok(RawResponse {
+            status: resp.status().as_u16(),
+            body: buf,
+        })
+    }
+
+    /// Runs the blocking curl request on a dedicated blocking thread via `spawn_blocking`, so a
+    /// hung CepLá connection can't stall the task polling the caller's `error_timeout` timer.
+    async fn get_curl(&self, url: &str, source: Source) -> Result<RawResponse, Error> {
+        let config = self.config.clone();
+        let url = url.to_string();
+
+        match tokio::task::spawn_blocking(move || Self::get_curl_blocking(&config, &url, source)).await {
+            Ok(result) => result,
+            Err(_) => Err(Error {
+                kind: Kind::UnexpectedLibraryError,
+                source,
+            }),
+        }
+    }
+
+    fn get_curl_blocking(config: &TransportConfig, url: &str, source: Source) -> Result<RawResponse, Error> {
+        let lib_error = || Error {
+            kind: Kind::UnexpectedLibraryError,
+            source,
+        };
+
+        let mut requester = Easy::new();
+        requester.url(url).or(Err(lib_error()))?;
+
+        let mut list = List::new();
+        list.append("Accept: application/json").or(Err(lib_error()))?;
+        requester.http_headers(list).or(Err(lib_error()))?;
+
+        if let Some(timeout) = config.connect_timeout {
+            requester.connect_timeout(timeout).or(Err(lib_error()))?;
+        }
+        if let Some(timeout) = config.read_timeout {
+            requester.timeout(timeout).or(Err(lib_error()))?;
+        }
+        if let Some(user_agent) = &config.user_agent {
+            requester.useragent(user_agent).or(Err(lib_error()))?;
+        }
+        if let Some(proxy) = &config.proxy {
+            requester.proxy(proxy).or(Err(lib_error()))?;
+        }
+
+        let mut buf = Vec::new();
+        {
+            let mut transfer = requester.transfer();
+            transfer
+                .write_function(|new_data| {
+                    buf.extend_from_slice(new_data);
+                    Ok(new_data.len())
+                })
+                .or(Err(Error {
+                    kind: Kind::MissingBodyError,
+                    source,
+                }))?;
+            transfer.perform().or(Err(Error {
+                kind: Kind::MissingBodyError,
+                source,
+            }))?;
+        }
+
+        let status = requester.response_code().or(Err(lib_error()))? as u16;
+        classify_status(status, source)?;
+
+        Ok(RawResponse { status, body: buf })
+    }
+}
+
+/// Maps an HTTP status code to the corresponding error [`Kind`], or `Ok(())` for 2xx.
+fn classify_status(code: u16, source: Source) -> Result<(), Error> {
+    match code {
+        200..=299 => Ok(()),
+        code @ 400..=499 => Err(Error {
+            kind: Kind::ClientError { code },
+            source,
+        }),
+        code @ 500..=599 => Err(Error {
+            kind: Kind::ServerError { code },
+            source,
+        }),
+        code => Err(Error {
+            kind: Kind::UnknownServerError { code },
+            source,
+        }),
+    }
+}